@@ -0,0 +1,136 @@
+//! Backup/restore of the full agent data set as a portable `.zip` archive.
+//!
+//! [`export_data`] bundles every data file plus a small manifest (schema
+//! version and creation time) into a timestamped archive; [`import_data`]
+//! validates that manifest, backs up the current files first, then atomically
+//! replaces them. The manifest's schema version lets future versions migrate
+//! older dumps forward.
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::ipc;
+
+/// Manifest schema version written into every archive.
+const SCHEMA_VERSION: u32 = 1;
+
+/// JSON documents that make up the agent's state.
+const DATA_FILES: &[&str] = &[
+    "tickets.json",
+    "agent.config.json",
+    "state-tracker.json",
+    "pattern-detector.json",
+    "pending-actions.json",
+];
+
+/// The rolling service log, stored under `logs/` in the data dir.
+const LOG_FILE: &str = "logs/agent.log";
+
+/// Every file eligible for backup/restore.
+fn backup_files() -> impl Iterator<Item = &'static str> {
+    DATA_FILES.iter().copied().chain(std::iter::once(LOG_FILE))
+}
+
+/// Bundle the data directory into `opsis-backup-<timestamp>.zip` inside
+/// `dest_dir`, returning the full path to the archive.
+#[tauri::command]
+pub fn export_data(dest_dir: String) -> Result<String, String> {
+    let data_dir = ipc::get_data_dir();
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let archive_path = Path::new(&dest_dir).join(format!("opsis-backup-{stamp}.zip"));
+
+    let file = std::fs::File::create(&archive_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut included = Vec::new();
+    for rel in backup_files() {
+        let Ok(bytes) = std::fs::read(data_dir.join(rel)) else {
+            continue;
+        };
+        zip.start_file(rel, options).map_err(|e| e.to_string())?;
+        zip.write_all(&bytes).map_err(|e| e.to_string())?;
+        included.push(rel.to_string());
+    }
+
+    let manifest = serde_json::json!({
+        "schemaVersion": SCHEMA_VERSION,
+        "createdAt": chrono::Utc::now().to_rfc3339(),
+        "files": included,
+    });
+    zip.start_file("manifest.json", options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(
+        serde_json::to_string_pretty(&manifest)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(archive_path.to_string_lossy().into_owned())
+}
+
+/// Restore a previously exported archive, overwriting the current data set.
+///
+/// The manifest is validated first; the current files are copied aside into a
+/// `.pre-import-<timestamp>` folder before each archived file is written to a
+/// temporary path and renamed into place.
+#[tauri::command]
+pub fn import_data(archive: String) -> Result<(), String> {
+    let file = std::fs::File::open(&archive).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    // Validate the manifest before touching anything on disk.
+    let manifest: serde_json::Value = {
+        let mut entry = zip
+            .by_name("manifest.json")
+            .map_err(|_| "archive is missing manifest.json".to_string())?;
+        let mut buf = String::new();
+        entry.read_to_string(&mut buf).map_err(|e| e.to_string())?;
+        serde_json::from_str(&buf).map_err(|e| format!("invalid manifest: {e}"))?
+    };
+    let version = manifest
+        .get("schemaVersion")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if version == 0 || version > u64::from(SCHEMA_VERSION) {
+        return Err(format!("unsupported backup schema version: {version}"));
+    }
+
+    let data_dir = ipc::get_data_dir();
+
+    // Back up the current files first, so a failed import is recoverable.
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let pre_import = data_dir.join(format!(".pre-import-{stamp}"));
+    std::fs::create_dir_all(&pre_import).map_err(|e| e.to_string())?;
+    for rel in backup_files() {
+        let src = data_dir.join(rel);
+        if src.exists() {
+            let _ = std::fs::copy(&src, pre_import.join(rel.replace('/', "_")));
+        }
+    }
+
+    // Extract each known file atomically (write to temp, then rename).
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        let name = entry.name().to_string();
+        if name == "manifest.json" || !backup_files().any(|f| f == name) {
+            continue;
+        }
+
+        let dest = data_dir.join(&name);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let tmp = dest.with_extension("import-tmp");
+        let mut out = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
+        std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        out.flush().map_err(|e| e.to_string())?;
+        drop(out);
+        std::fs::rename(&tmp, &dest).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
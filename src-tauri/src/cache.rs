@@ -0,0 +1,223 @@
+//! In-memory cache of the parsed data files, held in Tauri's managed state.
+//!
+//! Each document is loaded through the configured [`Storage`](crate::ipc::Storage)
+//! backend once and kept in memory alongside the source file's mtime; a
+//! subsequent read is served from memory and only re-parses when the file
+//! changes on disk or the cache is invalidated by the file watcher. While the
+//! file is missing, every read reloads. The hot summary numbers behind
+//! `get_stats` are additionally mirrored into [`AtomicI64`] fields that are
+//! refreshed whenever the tickets document reloads, so the stats command reads
+//! them without re-parsing. It still checks the tickets file's mtime and takes
+//! the tickets slot's read lock first, to keep the counters current.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::SystemTime;
+
+use crate::ipc;
+
+/// One cached document plus the file mtime it was parsed from.
+struct CachedFile {
+    loaded: bool,
+    mtime: Option<SystemTime>,
+    value: serde_json::Value,
+}
+
+impl Default for CachedFile {
+    fn default() -> Self {
+        Self {
+            loaded: false,
+            mtime: None,
+            value: serde_json::Value::Null,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DataCache {
+    tickets: RwLock<CachedFile>,
+    state: RwLock<CachedFile>,
+    patterns: RwLock<CachedFile>,
+    pending: RwLock<CachedFile>,
+    config: RwLock<CachedFile>,
+
+    // Hot summary counters served lock-free by `get_stats`.
+    issues_detected: AtomicI64,
+    active_tickets: AtomicI64,
+    issues_escalated: AtomicI64,
+    success_count: AtomicI64,
+    with_result: AtomicI64,
+}
+
+/// Process-global cache, also registered in Tauri's managed state so the file
+/// watcher and the IPC commands share a single instance.
+static CACHE: OnceLock<Arc<DataCache>> = OnceLock::new();
+
+/// Shared handle to the global cache.
+pub fn global() -> Arc<DataCache> {
+    CACHE.get_or_init(|| Arc::new(DataCache::default())).clone()
+}
+
+fn mtime(filename: &str) -> Option<SystemTime> {
+    std::fs::metadata(ipc::get_data_dir().join(filename))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+impl DataCache {
+    fn get<F>(
+        &self,
+        filename: &str,
+        slot: &RwLock<CachedFile>,
+        load: F,
+        refresh_counters: bool,
+    ) -> serde_json::Value
+    where
+        F: FnOnce() -> serde_json::Value,
+    {
+        let current = mtime(filename);
+        {
+            // Only serve from cache when we can observe the file's mtime. If the
+            // backing file is missing (`None`), reload every time rather than
+            // freezing on the first value — a `None == None` match would
+            // otherwise pin the cache to a stale (often empty) reading.
+            let guard = slot.read().unwrap();
+            if guard.loaded && current.is_some() && guard.mtime == current {
+                return guard.value.clone();
+            }
+        }
+
+        let value = load();
+        if refresh_counters {
+            self.refresh_counters(&value);
+        }
+        let mut guard = slot.write().unwrap();
+        guard.loaded = true;
+        guard.mtime = current;
+        guard.value = value.clone();
+        value
+    }
+
+    pub fn tickets(&self) -> serde_json::Value {
+        self.get(
+            "tickets.json",
+            &self.tickets,
+            || ipc::storage().load_tickets(),
+            true,
+        )
+    }
+
+    pub fn state(&self) -> serde_json::Value {
+        self.get(
+            "state-tracker.json",
+            &self.state,
+            || ipc::storage().load_state(),
+            false,
+        )
+    }
+
+    pub fn patterns(&self) -> serde_json::Value {
+        self.get(
+            "pattern-detector.json",
+            &self.patterns,
+            || ipc::storage().load_patterns(),
+            false,
+        )
+    }
+
+    pub fn pending(&self) -> serde_json::Value {
+        self.get(
+            "pending-actions.json",
+            &self.pending,
+            || ipc::storage().load_pending_actions(),
+            false,
+        )
+    }
+
+    pub fn config(&self) -> serde_json::Value {
+        self.get(
+            "agent.config.json",
+            &self.config,
+            || ipc::storage().load_config(),
+            false,
+        )
+    }
+
+    /// Force every slot to reload on its next access. Called by the file
+    /// watcher when it observes a write, keeping the cache correct.
+    pub fn invalidate_all(&self) {
+        for slot in [
+            &self.tickets,
+            &self.state,
+            &self.patterns,
+            &self.pending,
+            &self.config,
+        ] {
+            slot.write().unwrap().loaded = false;
+        }
+    }
+
+    fn refresh_counters(&self, tickets: &serde_json::Value) {
+        let Some(list) = tickets.get("tickets").and_then(|t| t.as_array()) else {
+            self.issues_detected.store(0, Ordering::Relaxed);
+            self.active_tickets.store(0, Ordering::Relaxed);
+            self.issues_escalated.store(0, Ordering::Relaxed);
+            self.success_count.store(0, Ordering::Relaxed);
+            self.with_result.store(0, Ordering::Relaxed);
+            return;
+        };
+
+        let total = list.len() as i64;
+        let active = list
+            .iter()
+            .filter(|t| {
+                let status = t.get("status").and_then(|s| s.as_str()).unwrap_or("");
+                let result = t.get("result").and_then(|s| s.as_str()).unwrap_or("");
+                status != "resolved" && result != "success"
+            })
+            .count() as i64;
+        let escalated = list
+            .iter()
+            .filter(|t| {
+                t.get("escalated")
+                    .map(|v| v.as_i64().unwrap_or(0) == 1 || v.as_bool().unwrap_or(false))
+                    .unwrap_or(false)
+            })
+            .count() as i64;
+        let success_count = list
+            .iter()
+            .filter(|t| t.get("result").and_then(|s| s.as_str()) == Some("success"))
+            .count() as i64;
+        let with_result = list
+            .iter()
+            .filter(|t| t.get("result").and_then(|s| s.as_str()).is_some())
+            .count() as i64;
+
+        self.issues_detected.store(total, Ordering::Relaxed);
+        self.active_tickets.store(active, Ordering::Relaxed);
+        self.issues_escalated.store(escalated, Ordering::Relaxed);
+        self.success_count.store(success_count, Ordering::Relaxed);
+        self.with_result.store(with_result, Ordering::Relaxed);
+    }
+
+    pub fn issues_detected(&self) -> i64 {
+        self.issues_detected.load(Ordering::Relaxed)
+    }
+
+    pub fn active_tickets(&self) -> i64 {
+        self.active_tickets.load(Ordering::Relaxed)
+    }
+
+    pub fn issues_escalated(&self) -> i64 {
+        self.issues_escalated.load(Ordering::Relaxed)
+    }
+
+    pub fn success_rate(&self) -> i64 {
+        let with_result = self.with_result.load(Ordering::Relaxed);
+        if with_result > 0 {
+            self.success_count.load(Ordering::Relaxed) * 100 / with_result
+        } else {
+            0
+        }
+    }
+}
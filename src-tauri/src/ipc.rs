@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 
+use crate::cache;
+
 /// Locate the data directory.
 /// Checks: 1) next to exe  2) parent of exe  3) CWD
-fn get_data_dir() -> PathBuf {
+pub(crate) fn get_data_dir() -> PathBuf {
     if let Ok(exe) = std::env::current_exe() {
         // Next to exe: <exe_dir>/data/
         let beside_exe = exe.parent().unwrap_or(exe.as_ref()).join("data");
@@ -28,6 +31,382 @@ fn read_json_file(filename: &str) -> Option<serde_json::Value> {
     serde_json::from_str(&content).ok()
 }
 
+// ---------- Storage backend ----------
+
+/// Abstraction over the agent's on-disk data so that IPC commands go through a
+/// single swappable backend instead of each re-reading and rewriting a whole
+/// JSON file inline.
+///
+/// Two backends are provided and selected once at startup by [`storage`] from
+/// the `storageBackend` setting in `agent.config.json`: [`JsonStorage`], the
+/// original file layout, and [`SqliteStorage`], which keeps tickets in an
+/// indexed table so `clear_old_tickets` is a single `DELETE WHERE timestamp < ?`
+/// and `submit_manual_ticket` an `INSERT`, instead of rewriting the whole file
+/// and racing the service process.
+pub trait Storage: Send + Sync {
+    /// Whole tickets document, shaped `{ "tickets": [ ... ] }`.
+    fn load_tickets(&self) -> serde_json::Value;
+    /// Insert a single ticket at the front of the list.
+    fn append_ticket(&self, ticket: serde_json::Value) -> bool;
+    /// Drop every ticket older than `cutoff` (an RFC3339 timestamp),
+    /// returning how many were removed.
+    fn retain_tickets(&self, cutoff: &str) -> i64;
+    /// Remove every ticket.
+    fn clear_tickets(&self) -> bool;
+    /// Whole agent configuration object.
+    fn load_config(&self) -> serde_json::Value;
+    /// Merge the given keys into the configuration, overwriting existing ones.
+    fn merge_config(&self, patch: serde_json::Map<String, serde_json::Value>) -> bool;
+    /// Raw `state-tracker.json` document.
+    fn load_state(&self) -> serde_json::Value;
+    /// Raw `pattern-detector.json` document.
+    fn load_patterns(&self) -> serde_json::Value;
+    /// Raw `pending-actions.json` document.
+    fn load_pending_actions(&self) -> serde_json::Value;
+}
+
+/// The process-wide storage backend, built once on first use rather than
+/// reconstructed on every IPC call (and every cache reload).
+pub fn storage() -> &'static dyn Storage {
+    static STORAGE: std::sync::OnceLock<Box<dyn Storage>> = std::sync::OnceLock::new();
+    STORAGE.get_or_init(build_storage).as_ref()
+}
+
+/// Select the backend from the `storageBackend` setting, falling back to the
+/// JSON-file backend when SQLite is not requested or cannot be opened.
+fn build_storage() -> Box<dyn Storage> {
+    let backend = read_json_file("agent.config.json")
+        .and_then(|c| c.get("storageBackend").and_then(|v| v.as_str()).map(str::to_owned))
+        .unwrap_or_default();
+
+    if backend == "sqlite" {
+        if let Some(store) = SqliteStorage::open() {
+            return Box::new(store);
+        }
+    }
+    Box::new(JsonStorage)
+}
+
+// ---------- JSON-file backend ----------
+
+/// The original backend: each logical document is a JSON file in the data dir.
+pub struct JsonStorage;
+
+impl JsonStorage {
+    fn read(&self, filename: &str) -> serde_json::Value {
+        read_json_file(filename).unwrap_or(serde_json::json!({}))
+    }
+}
+
+impl Storage for JsonStorage {
+    fn load_tickets(&self) -> serde_json::Value {
+        read_json_file("tickets.json").unwrap_or_else(|| serde_json::json!({ "tickets": [] }))
+    }
+
+    fn append_ticket(&self, ticket: serde_json::Value) -> bool {
+        let path = get_data_dir().join("tickets.json");
+        let content =
+            std::fs::read_to_string(&path).unwrap_or_else(|_| r#"{"tickets":[]}"#.to_string());
+        let mut data: serde_json::Value =
+            serde_json::from_str(&content).unwrap_or(serde_json::json!({ "tickets": [] }));
+
+        if let Some(tickets) = data.get_mut("tickets").and_then(|t| t.as_array_mut()) {
+            tickets.insert(0, ticket);
+        }
+
+        match serde_json::to_string_pretty(&data) {
+            Ok(json) => std::fs::write(&path, json).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    fn retain_tickets(&self, cutoff: &str) -> i64 {
+        let path = get_data_dir().join("tickets.json");
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return 0;
+        };
+        let Ok(mut data) = serde_json::from_str::<serde_json::Value>(&content) else {
+            return 0;
+        };
+
+        let removed;
+        if let Some(tickets) = data.get_mut("tickets").and_then(|t| t.as_array_mut()) {
+            let before = tickets.len();
+            tickets.retain(|t| {
+                let ts = t
+                    .get("timestamp")
+                    .or_else(|| t.get("created_at"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                ts >= cutoff
+            });
+            removed = (before - tickets.len()) as i64;
+        } else {
+            return 0;
+        }
+
+        if let Ok(json) = serde_json::to_string_pretty(&data) {
+            let _ = std::fs::write(&path, json);
+        }
+        removed
+    }
+
+    fn clear_tickets(&self) -> bool {
+        let path = get_data_dir().join("tickets.json");
+        std::fs::write(&path, r#"{"tickets":[]}"#).is_ok()
+    }
+
+    fn load_config(&self) -> serde_json::Value {
+        read_json_file("agent.config.json").unwrap_or(serde_json::json!({}))
+    }
+
+    fn merge_config(&self, patch: serde_json::Map<String, serde_json::Value>) -> bool {
+        let path = get_data_dir().join("agent.config.json");
+        let mut config = self.load_config();
+        let Some(obj) = config.as_object_mut() else {
+            return false;
+        };
+        for (k, v) in patch {
+            obj.insert(k, v);
+        }
+        match serde_json::to_string_pretty(&config) {
+            Ok(json) => std::fs::write(&path, json).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    fn load_state(&self) -> serde_json::Value {
+        self.read("state-tracker.json")
+    }
+
+    fn load_patterns(&self) -> serde_json::Value {
+        self.read("pattern-detector.json")
+    }
+
+    fn load_pending_actions(&self) -> serde_json::Value {
+        self.read("pending-actions.json")
+    }
+}
+
+// ---------- SQLite backend ----------
+
+/// SQLite-backed storage. Tickets live in a table indexed by timestamp,
+/// configuration is a key/value table, and the service-owned documents (state
+/// tracker, pattern detector, pending actions) are JSON blobs in a `documents`
+/// table. Mutations touch a single row, so `clear_old_tickets` is one
+/// `DELETE WHERE timestamp < ?` and a manual ticket is one `INSERT` — no
+/// full-file rewrite and no read-modify-write race with the service process.
+///
+/// On open, an empty tickets or config table is seeded from the existing JSON
+/// file. Tickets are read from `tickets.json` only while the tickets table is
+/// empty, and each service document is read from its JSON file while it has no
+/// row. Tickets or config that the service writes to the JSON files after
+/// seeding are not picked up.
+pub struct SqliteStorage {
+    conn: std::sync::Mutex<rusqlite::Connection>,
+}
+
+impl SqliteStorage {
+    /// Open (creating if needed) `data/agent.db`, ensure the schema exists, and
+    /// seed it from the JSON files the first time around.
+    fn open() -> Option<Self> {
+        let conn = rusqlite::Connection::open(get_data_dir().join("agent.db")).ok()?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tickets (
+                 ticket_id TEXT PRIMARY KEY,
+                 timestamp TEXT NOT NULL,
+                 body      TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS idx_tickets_timestamp ON tickets(timestamp);
+             CREATE TABLE IF NOT EXISTS config (
+                 key   TEXT PRIMARY KEY,
+                 value TEXT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS documents (
+                 name TEXT PRIMARY KEY,
+                 body TEXT NOT NULL
+             );",
+        )
+        .ok()?;
+
+        let store = Self {
+            conn: std::sync::Mutex::new(conn),
+        };
+        store.seed();
+        Some(store)
+    }
+
+    /// Import existing JSON files into any table that is still empty, so an
+    /// upgraded install keeps its service-written history.
+    fn seed(&self) {
+        let conn = self.conn.lock().unwrap();
+
+        let ticket_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM tickets", [], |row| row.get(0))
+            .unwrap_or(0);
+        if ticket_count == 0 {
+            if let Some(tickets) = read_json_file("tickets.json")
+                .and_then(|d| d.get("tickets").and_then(|t| t.as_array()).cloned())
+            {
+                for ticket in &tickets {
+                    let _ = conn.execute(
+                        "INSERT OR REPLACE INTO tickets (ticket_id, timestamp, body) VALUES (?1, ?2, ?3)",
+                        rusqlite::params![
+                            Self::ticket_id(ticket),
+                            Self::ticket_timestamp(ticket),
+                            ticket.to_string()
+                        ],
+                    );
+                }
+            }
+        }
+
+        let config_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM config", [], |row| row.get(0))
+            .unwrap_or(0);
+        if config_count == 0 {
+            if let Some(obj) =
+                read_json_file("agent.config.json").and_then(|c| c.as_object().cloned())
+            {
+                for (k, v) in &obj {
+                    let _ = conn.execute(
+                        "INSERT OR REPLACE INTO config (key, value) VALUES (?1, ?2)",
+                        rusqlite::params![k, v.to_string()],
+                    );
+                }
+            }
+        }
+    }
+
+    /// Read a service document from the `documents` table, falling back to the
+    /// JSON file when no row has been written yet.
+    fn load_document(&self, name: &str, fallback: &str) -> serde_json::Value {
+        let row: Option<String> = {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT body FROM documents WHERE name = ?1",
+                [name],
+                |r| r.get(0),
+            )
+            .ok()
+        };
+        row.and_then(|body| serde_json::from_str(&body).ok())
+            .or_else(|| read_json_file(fallback))
+            .unwrap_or(serde_json::json!({}))
+    }
+
+    fn ticket_id(ticket: &serde_json::Value) -> String {
+        ticket
+            .get("ticket_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    }
+
+    fn ticket_timestamp(ticket: &serde_json::Value) -> String {
+        ticket
+            .get("timestamp")
+            .or_else(|| ticket.get("created_at"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn load_tickets(&self) -> serde_json::Value {
+        let conn = self.conn.lock().unwrap();
+        let tickets: Vec<serde_json::Value> = conn
+            .prepare("SELECT body FROM tickets ORDER BY timestamp DESC")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| row.get::<_, String>(0)).map(|rows| {
+                    rows.filter_map(Result::ok)
+                        .filter_map(|body| serde_json::from_str(&body).ok())
+                        .collect()
+                })
+            })
+            .unwrap_or_default();
+
+        // Read-through to the JSON file until the first ticket lands in the DB.
+        if tickets.is_empty() {
+            drop(conn);
+            return read_json_file("tickets.json")
+                .unwrap_or_else(|| serde_json::json!({ "tickets": [] }));
+        }
+        serde_json::json!({ "tickets": tickets })
+    }
+
+    fn append_ticket(&self, ticket: serde_json::Value) -> bool {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO tickets (ticket_id, timestamp, body) VALUES (?1, ?2, ?3)",
+            rusqlite::params![
+                Self::ticket_id(&ticket),
+                Self::ticket_timestamp(&ticket),
+                ticket.to_string()
+            ],
+        )
+        .is_ok()
+    }
+
+    fn retain_tickets(&self, cutoff: &str) -> i64 {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM tickets WHERE timestamp < ?1", [cutoff])
+            .map(|n| n as i64)
+            .unwrap_or(0)
+    }
+
+    fn clear_tickets(&self) -> bool {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM tickets", []).is_ok()
+    }
+
+    fn load_config(&self) -> serde_json::Value {
+        let conn = self.conn.lock().unwrap();
+        let mut obj = serde_json::Map::new();
+        if let Ok(mut stmt) = conn.prepare("SELECT key, value FROM config") {
+            if let Ok(rows) = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            }) {
+                for (key, raw) in rows.flatten() {
+                    let value =
+                        serde_json::from_str(&raw).unwrap_or(serde_json::Value::String(raw));
+                    obj.insert(key, value);
+                }
+            }
+        }
+        serde_json::Value::Object(obj)
+    }
+
+    fn merge_config(&self, patch: serde_json::Map<String, serde_json::Value>) -> bool {
+        let conn = self.conn.lock().unwrap();
+        for (k, v) in patch {
+            if conn
+                .execute(
+                    "INSERT OR REPLACE INTO config (key, value) VALUES (?1, ?2)",
+                    rusqlite::params![k, v.to_string()],
+                )
+                .is_err()
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn load_state(&self) -> serde_json::Value {
+        self.load_document("state-tracker", "state-tracker.json")
+    }
+
+    fn load_patterns(&self) -> serde_json::Value {
+        self.load_document("pattern-detector", "pattern-detector.json")
+    }
+
+    fn load_pending_actions(&self) -> serde_json::Value {
+        self.load_document("pending-actions", "pending-actions.json")
+    }
+}
+
 // ---------- Stats ----------
 
 #[derive(Serialize)]
@@ -41,59 +420,16 @@ pub struct Stats {
 
 #[tauri::command]
 pub fn get_stats() -> Stats {
-    let default = Stats {
-        issues_detected: 0,
-        active_tickets: 0,
-        issues_escalated: 0,
-        success_rate: 0,
-    };
-
-    let Some(data) = read_json_file("tickets.json") else {
-        return default;
-    };
-
-    let tickets = match data.get("tickets").and_then(|t| t.as_array()) {
-        Some(t) => t,
-        None => return default,
-    };
-
-    let total = tickets.len() as i64;
-    let active = tickets
-        .iter()
-        .filter(|t| {
-            let status = t.get("status").and_then(|s| s.as_str()).unwrap_or("");
-            let result = t.get("result").and_then(|s| s.as_str()).unwrap_or("");
-            status != "resolved" && result != "success"
-        })
-        .count() as i64;
-    let escalated = tickets
-        .iter()
-        .filter(|t| {
-            t.get("escalated")
-                .map(|v| v.as_i64().unwrap_or(0) == 1 || v.as_bool().unwrap_or(false))
-                .unwrap_or(false)
-        })
-        .count() as i64;
-    let success_count = tickets
-        .iter()
-        .filter(|t| t.get("result").and_then(|s| s.as_str()) == Some("success"))
-        .count() as i64;
-    let with_result = tickets
-        .iter()
-        .filter(|t| t.get("result").and_then(|s| s.as_str()).is_some())
-        .count() as i64;
-
-    let success_rate = if with_result > 0 {
-        (success_count * 100) / with_result
-    } else {
-        0
-    };
+    // Ensure the tickets document (and its derived counters) is current, then
+    // serve the summary numbers straight from the atomic fields.
+    let cache = cache::global();
+    cache.tickets();
 
     Stats {
-        issues_detected: total,
-        active_tickets: active,
-        issues_escalated: escalated,
-        success_rate,
+        issues_detected: cache.issues_detected(),
+        active_tickets: cache.active_tickets(),
+        issues_escalated: cache.issues_escalated(),
+        success_rate: cache.success_rate(),
     }
 }
 
@@ -101,9 +437,7 @@ pub fn get_stats() -> Stats {
 
 #[tauri::command]
 pub fn get_tickets() -> Vec<serde_json::Value> {
-    let Some(data) = read_json_file("tickets.json") else {
-        return vec![];
-    };
+    let data = cache::global().tickets();
 
     match data.get("tickets").and_then(|t| t.as_array()) {
         Some(tickets) => {
@@ -120,44 +454,20 @@ pub fn get_tickets() -> Vec<serde_json::Value> {
 
 #[tauri::command]
 pub fn clear_old_tickets() -> i64 {
-    let path = get_data_dir().join("tickets.json");
-    let content = match std::fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(_) => return 0,
-    };
-
-    let mut data: serde_json::Value = match serde_json::from_str(&content) {
-        Ok(d) => d,
-        Err(_) => return 0,
-    };
-
     let one_day_ago = chrono::Utc::now() - chrono::Duration::hours(24);
     let cutoff = one_day_ago.to_rfc3339();
+    let removed = storage().retain_tickets(&cutoff);
+    cache::global().invalidate_all();
+    removed
+}
 
-    let original_count;
-    let new_count;
-
-    if let Some(tickets) = data.get_mut("tickets").and_then(|t| t.as_array_mut()) {
-        original_count = tickets.len();
-        tickets.retain(|t| {
-            let ts = t
-                .get("timestamp")
-                .or_else(|| t.get("created_at"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            ts >= cutoff.as_str()
-        });
-        new_count = tickets.len();
-    } else {
-        return 0;
-    }
-
-    // Write back
-    if let Ok(json) = serde_json::to_string_pretty(&data) {
-        let _ = std::fs::write(&path, json);
-    }
+// ---------- Clear all tickets ----------
 
-    (original_count - new_count) as i64
+#[tauri::command]
+pub fn clear_all_tickets() -> bool {
+    let ok = storage().clear_tickets();
+    cache::global().invalidate_all();
+    ok
 }
 
 // ---------- Submit manual ticket ----------
@@ -174,12 +484,6 @@ pub struct ManualTicket {
 
 #[tauri::command]
 pub fn submit_manual_ticket(ticket: ManualTicket) -> bool {
-    let path = get_data_dir().join("tickets.json");
-    let content = std::fs::read_to_string(&path).unwrap_or_else(|_| r#"{"tickets":[]}"#.to_string());
-
-    let mut data: serde_json::Value =
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({"tickets": []}));
-
     let new_ticket = serde_json::json!({
         "ticket_id": format!("manual-{}", chrono::Utc::now().timestamp_millis()),
         "timestamp": ticket.submitted_at,
@@ -192,14 +496,9 @@ pub fn submit_manual_ticket(ticket: ManualTicket) -> bool {
         "computer_name": ticket.server_name,
     });
 
-    if let Some(tickets) = data.get_mut("tickets").and_then(|t| t.as_array_mut()) {
-        tickets.insert(0, new_ticket);
-    }
-
-    match serde_json::to_string_pretty(&data) {
-        Ok(json) => std::fs::write(&path, json).is_ok(),
-        Err(_) => false,
-    }
+    let ok = storage().append_ticket(new_ticket);
+    cache::global().invalidate_all();
+    ok
 }
 
 // ---------- Update settings ----------
@@ -216,43 +515,33 @@ pub struct SettingsUpdate {
 
 #[tauri::command]
 pub fn update_settings(settings: SettingsUpdate) -> bool {
-    let path = get_data_dir().join("agent.config.json");
-
-    let mut config: serde_json::Value = if path.exists() {
-        let content = std::fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-
-    let obj = config.as_object_mut().unwrap();
+    let mut patch = serde_json::Map::new();
     if let Some(v) = settings.server_url {
-        obj.insert("serverUrl".into(), serde_json::json!(v));
+        patch.insert("serverUrl".into(), serde_json::json!(v));
     }
     if let Some(v) = settings.monitor_interval {
-        obj.insert("monitorInterval".into(), serde_json::json!(v));
+        patch.insert("monitorInterval".into(), serde_json::json!(v));
     }
     if let Some(v) = settings.alert_email {
-        obj.insert("alertEmail".into(), serde_json::json!(v));
+        patch.insert("alertEmail".into(), serde_json::json!(v));
     }
     if let Some(v) = settings.log_retention {
-        obj.insert("logRetention".into(), serde_json::json!(v));
+        patch.insert("logRetention".into(), serde_json::json!(v));
     }
     if let Some(v) = settings.confidence_threshold {
-        obj.insert("confidenceThreshold".into(), serde_json::json!(v));
+        patch.insert("confidenceThreshold".into(), serde_json::json!(v));
     }
 
-    match serde_json::to_string_pretty(&config) {
-        Ok(json) => std::fs::write(&path, json).is_ok(),
-        Err(_) => false,
-    }
+    let ok = storage().merge_config(patch);
+    cache::global().invalidate_all();
+    ok
 }
 
 // ---------- Load settings (for frontend) ----------
 
 #[tauri::command]
 pub fn get_settings() -> serde_json::Value {
-    read_json_file("agent.config.json").unwrap_or(serde_json::json!({}))
+    cache::global().config()
 }
 
 // ---------- Health data ----------
@@ -275,16 +564,207 @@ pub fn get_health_data() -> HealthData {
 
     HealthData {
         health_scores,
-        correlations: serde_json::json!({}),
+        correlations: build_correlations(),
         patterns,
         proactive_actions,
     }
 }
 
-fn build_health_scores() -> serde_json::Value {
-    let Some(data) = read_json_file("state-tracker.json") else {
-        return serde_json::json!({});
+// ---------- Correlations ----------
+
+/// Width of a time bucket, in seconds, used to align events onto a grid.
+const BIN_SECONDS: i64 = 300;
+/// Keep only pairs whose lift exceeds this threshold.
+const LIFT_THRESHOLD: f64 = 1.5;
+/// Require at least this many shared bins before reporting a pair, so rare
+/// events don't produce spurious edges.
+const MIN_CO_OCCURRENCES: usize = 3;
+
+/// Temporal correlation analysis linking resources/signals that degrade
+/// together.
+///
+/// Events (a resource transitioning into `warning`/`error`/`critical`, or a
+/// detected pattern occurrence) are bucketed into fixed [`BIN_SECONDS`] bins to
+/// form, per item, a binary occurrence vector across bins. For each pair we
+/// compute the lift `P(A∧B) / (P(A)·P(B))` and the phi coefficient from the
+/// 2×2 contingency counts, keeping pairs above [`LIFT_THRESHOLD`] with at least
+/// [`MIN_CO_OCCURRENCES`] shared bins. The result is an edge list the UI can
+/// use to surface "these resources fail together".
+fn build_correlations() -> serde_json::Value {
+    let mut items: HashMap<String, BTreeSet<i64>> = HashMap::new();
+
+    // State transitions into a degraded severity.
+    let state = cache::global().state();
+    if let Some(resources) = state.get("resources").and_then(|r| r.as_object()) {
+        for (key, resource) in resources {
+            let name = key.split(':').nth(1).unwrap_or(key).to_string();
+            collect_state_events(resource, &name, &mut items);
+        }
+    }
+
+    // Pattern-detector occurrences.
+    let patterns = cache::global().patterns();
+    if let Some(map) = patterns.get("patterns").and_then(|p| p.as_object()) {
+        for (key, val) in map {
+            collect_pattern_events(val, key, &mut items);
+        }
+    }
+
+    // Universe of bins spanned by the timeline.
+    let all_bins = items.values().flatten().copied().collect::<BTreeSet<i64>>();
+    let total = match (all_bins.first(), all_bins.last()) {
+        (Some(first), Some(last)) => (last - first + 1) as usize,
+        _ => 0,
     };
+    if total == 0 || items.len() < 2 {
+        return serde_json::json!({ "edges": [] });
+    }
+
+    let names: Vec<&String> = items.keys().collect();
+    let mut edges = Vec::new();
+    for i in 0..names.len() {
+        for j in (i + 1)..names.len() {
+            let a = &items[names[i]];
+            let b = &items[names[j]];
+            let co = a.intersection(b).count();
+            if co < MIN_CO_OCCURRENCES {
+                continue;
+            }
+
+            let pa = a.len() as f64 / total as f64;
+            let pb = b.len() as f64 / total as f64;
+            if pa <= 0.0 || pb <= 0.0 {
+                continue;
+            }
+            let lift = (co as f64 / total as f64) / (pa * pb);
+            if lift <= LIFT_THRESHOLD {
+                continue;
+            }
+
+            edges.push(serde_json::json!({
+                "source": names[i],
+                "target": names[j],
+                "lift": round2(lift),
+                "coOccurrences": co,
+                "phi": round2(phi(co, a.len(), b.len(), total)),
+            }));
+        }
+    }
+
+    // Strongest correlations first.
+    edges.sort_by(|x, y| {
+        let lx = x.get("lift").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let ly = y.get("lift").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        ly.partial_cmp(&lx).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    serde_json::json!({ "edges": edges })
+}
+
+fn is_degraded(severity: &str) -> bool {
+    matches!(severity, "warning" | "error" | "critical")
+}
+
+fn parse_bin(ts: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(ts)
+        .ok()
+        .map(|dt| dt.timestamp().div_euclid(BIN_SECONDS))
+}
+
+fn collect_state_events(
+    resource: &serde_json::Value,
+    name: &str,
+    items: &mut HashMap<String, BTreeSet<i64>>,
+) {
+    // Prefer an explicit transition history when the tracker records one.
+    if let Some(history) = resource.get("history").and_then(|h| h.as_array()) {
+        for entry in history {
+            let severity = entry
+                .get("severityLevel")
+                .or_else(|| entry.get("state"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            if !is_degraded(severity) {
+                continue;
+            }
+            let ts = entry
+                .get("timestamp")
+                .or_else(|| entry.get("changedAt"))
+                .and_then(|v| v.as_str());
+            if let Some(bin) = ts.and_then(parse_bin) {
+                items.entry(name.to_string()).or_default().insert(bin);
+            }
+        }
+        return;
+    }
+
+    // Fallback: the current severity with a last-changed timestamp.
+    let severity = resource
+        .get("severityLevel")
+        .and_then(|v| v.as_str())
+        .unwrap_or("info");
+    if !is_degraded(severity) {
+        return;
+    }
+    let ts = resource
+        .get("lastTransition")
+        .or_else(|| resource.get("lastChanged"))
+        .or_else(|| resource.get("updatedAt"))
+        .and_then(|v| v.as_str());
+    if let Some(bin) = ts.and_then(parse_bin) {
+        items.entry(name.to_string()).or_default().insert(bin);
+    }
+}
+
+fn collect_pattern_events(
+    pattern: &serde_json::Value,
+    key: &str,
+    items: &mut HashMap<String, BTreeSet<i64>>,
+) {
+    if let Some(occurrences) = pattern.get("occurrences").and_then(|o| o.as_array()) {
+        for occ in occurrences {
+            let ts = occ
+                .as_str()
+                .or_else(|| occ.get("timestamp").and_then(|v| v.as_str()));
+            if let Some(bin) = ts.and_then(parse_bin) {
+                items.entry(key.to_string()).or_default().insert(bin);
+            }
+        }
+        return;
+    }
+
+    // Fallback: a single last-seen timestamp.
+    let ts = pattern
+        .get("lastSeen")
+        .or_else(|| pattern.get("lastOccurrence"))
+        .and_then(|v| v.as_str());
+    if let Some(bin) = ts.and_then(parse_bin) {
+        items.entry(key.to_string()).or_default().insert(bin);
+    }
+}
+
+/// Phi coefficient from the 2×2 contingency of two occurrence vectors.
+fn phi(co: usize, a: usize, b: usize, total: usize) -> f64 {
+    let n11 = co as f64;
+    let n10 = (a - co) as f64;
+    let n01 = (b - co) as f64;
+    // |¬A ∧ ¬B| = total - |A ∪ B| = total + co - a - b. Fold `+ co` in before
+    // subtracting so the usize arithmetic can't underflow when a + b > total.
+    let n00 = (total + co).saturating_sub(a + b) as f64;
+    let denom = ((n11 + n10) * (n01 + n00) * (n11 + n01) * (n10 + n00)).sqrt();
+    if denom == 0.0 {
+        0.0
+    } else {
+        (n11 * n00 - n10 * n01) / denom
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+pub(crate) fn build_health_scores() -> serde_json::Value {
+    let data = cache::global().state();
 
     let Some(resources) = data.get("resources").and_then(|r| r.as_object()) else {
         return serde_json::json!({});
@@ -335,9 +815,7 @@ fn build_health_scores() -> serde_json::Value {
 }
 
 fn build_patterns() -> Vec<serde_json::Value> {
-    let Some(data) = read_json_file("pattern-detector.json") else {
-        return vec![];
-    };
+    let data = cache::global().patterns();
 
     let Some(patterns) = data.get("patterns").and_then(|p| p.as_object()) else {
         return vec![];
@@ -360,15 +838,15 @@ fn build_patterns() -> Vec<serde_json::Value> {
         .collect()
 }
 
-fn build_proactive_actions() -> Vec<serde_json::Value> {
-    let Some(data) = read_json_file("pending-actions.json") else {
-        return vec![];
-    };
+pub(crate) fn build_proactive_actions() -> Vec<serde_json::Value> {
+    let data = cache::global().pending();
 
     let Some(actions) = data.get("pending_actions").and_then(|a| a.as_array()) else {
         return vec![];
     };
 
+    let tasks = load_action_tasks();
+
     actions
         .iter()
         .take(10)
@@ -378,11 +856,139 @@ fn build_proactive_actions() -> Vec<serde_json::Value> {
                 .and_then(|s| s.get("severity"))
                 .and_then(|s| s.as_str())
                 .unwrap_or("low");
+            let id = action_id(a);
+            let status = tasks
+                .get("tasks")
+                .and_then(|t| t.get(&id))
+                .and_then(|t| t.get("status"))
+                .and_then(|s| s.as_str())
+                .unwrap_or("pending");
             serde_json::json!({
+                "id": id,
                 "title": a.get("signature_id").and_then(|v| v.as_str()).unwrap_or("Action"),
                 "urgency": severity,
                 "reasoning": a.get("server_message").and_then(|v| v.as_str()).unwrap_or(""),
+                "status": status,
             })
         })
         .collect()
 }
+
+// ---------- Proactive-action approval workflow ----------
+
+/// Sibling document tracking the lifecycle of each proactive action.
+const ACTION_TASKS_FILE: &str = "action-tasks.json";
+
+/// Stable identifier for a pending action.
+///
+/// Only a per-action `id` is treated as the task key. `signature_id` is *not*
+/// used: several distinct pending actions can share one signature, so keying on
+/// it would collapse them into a single task and approve/dismiss them together.
+/// When no `id` is present, the key is synthesized from the action's contents
+/// so distinct actions stay distinct.
+fn action_id(action: &serde_json::Value) -> String {
+    if let Some(id) = action
+        .get("id")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+    {
+        return id.to_string();
+    }
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    std::hash::Hash::hash(&action.to_string(), &mut hasher);
+    format!("action-{:016x}", std::hash::Hasher::finish(&hasher))
+}
+
+fn load_action_tasks() -> serde_json::Value {
+    read_json_file(ACTION_TASKS_FILE)
+        .unwrap_or_else(|| serde_json::json!({ "tasks": {}, "queue": [] }))
+}
+
+fn save_action_tasks(doc: &serde_json::Value) -> bool {
+    let path = get_data_dir().join(ACTION_TASKS_FILE);
+    match serde_json::to_string_pretty(doc) {
+        Ok(json) => std::fs::write(path, json).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Ensure the document has the expected `tasks`/`queue` containers.
+fn ensure_shape(doc: &mut serde_json::Value) {
+    if !doc.is_object() {
+        *doc = serde_json::json!({});
+    }
+    let obj = doc.as_object_mut().unwrap();
+    obj.entry("tasks").or_insert_with(|| serde_json::json!({}));
+    obj.entry("queue").or_insert_with(|| serde_json::json!([]));
+}
+
+/// Record a state change for `id`, appending a timestamped history entry that
+/// captures the actor (`user` vs `auto`) and a reason for auditability.
+fn apply_transition(doc: &mut serde_json::Value, id: &str, status: &str, actor: &str, reason: &str) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let Some(tasks) = doc.get_mut("tasks").and_then(|t| t.as_object_mut()) else {
+        return;
+    };
+    let task = tasks
+        .entry(id.to_string())
+        .or_insert_with(|| serde_json::json!({ "history": [] }));
+    task["status"] = serde_json::json!(status);
+    task["updatedAt"] = serde_json::json!(now);
+    if let Some(history) = task.get_mut("history").and_then(|h| h.as_array_mut()) {
+        history.push(serde_json::json!({
+            "status": status,
+            "actor": actor,
+            "reason": reason,
+            "timestamp": now,
+        }));
+    }
+}
+
+fn enqueue(doc: &mut serde_json::Value, id: &str) {
+    if let Some(queue) = doc.get_mut("queue").and_then(|q| q.as_array_mut()) {
+        if !queue.iter().any(|v| v.as_str() == Some(id)) {
+            queue.push(serde_json::json!(id));
+        }
+    }
+}
+
+fn dequeue(doc: &mut serde_json::Value, id: &str) {
+    if let Some(queue) = doc.get_mut("queue").and_then(|q| q.as_array_mut()) {
+        queue.retain(|v| v.as_str() != Some(id));
+    }
+}
+
+/// Approve an action: move it to `approved` and enqueue it for the service
+/// process to pick up and execute.
+#[tauri::command]
+pub fn approve_action(id: String, reason: Option<String>) -> bool {
+    let reason = reason.unwrap_or_else(|| "approved by operator".to_string());
+    let mut doc = load_action_tasks();
+    ensure_shape(&mut doc);
+    apply_transition(&mut doc, &id, "approved", "user", &reason);
+    enqueue(&mut doc, &id);
+    save_action_tasks(&doc)
+}
+
+/// Dismiss an action: mark it `dismissed` and drop it from the queue.
+#[tauri::command]
+pub fn dismiss_action(id: String, reason: Option<String>) -> bool {
+    let reason = reason.unwrap_or_else(|| "dismissed by operator".to_string());
+    let mut doc = load_action_tasks();
+    ensure_shape(&mut doc);
+    apply_transition(&mut doc, &id, "dismissed", "user", &reason);
+    dequeue(&mut doc, &id);
+    save_action_tasks(&doc)
+}
+
+/// Current status and transition history for an action, defaulting to
+/// `pending` for actions that have never been acted on.
+#[tauri::command]
+pub fn get_action_status(id: String) -> serde_json::Value {
+    load_action_tasks()
+        .get("tasks")
+        .and_then(|t| t.get(&id))
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({ "status": "pending", "history": [] }))
+}
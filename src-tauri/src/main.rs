@@ -1,7 +1,11 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod backup;
+mod cache;
 mod ipc;
+mod metrics;
+mod watcher;
 
 use tauri::{
     menu::{MenuBuilder, MenuItemBuilder},
@@ -24,6 +28,8 @@ fn main() {
             let show_item = MenuItemBuilder::with_id("show", "Show Control Panel").build(app)?;
             let view_logs_item =
                 MenuItemBuilder::with_id("view_logs", "View Logs").build(app)?;
+            let backup_item =
+                MenuItemBuilder::with_id("backup_data", "Backup Data…").build(app)?;
             let restart_item =
                 MenuItemBuilder::with_id("restart_service", "Restart Service").build(app)?;
             let quit_item = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
@@ -35,6 +41,7 @@ fn main() {
                 .item(&show_item)
                 .item(&get_help_item)
                 .item(&view_logs_item)
+                .item(&backup_item)
                 .separator()
                 .item(&restart_item)
                 .separator()
@@ -70,6 +77,20 @@ fn main() {
                                 }
                             }
                         }
+                        "backup_data" => {
+                            // Write a timestamped archive next to the executable
+                            // and open it with its default application.
+                            let dest = std::env::current_exe()
+                                .ok()
+                                .and_then(|e| e.parent().map(|p| p.to_path_buf()))
+                                .unwrap_or_else(|| std::path::PathBuf::from("."));
+                            match backup::export_data(dest.to_string_lossy().into_owned()) {
+                                Ok(path) => {
+                                    let _ = open::that(&path);
+                                }
+                                Err(e) => eprintln!("backup failed: {e}"),
+                            }
+                        }
                         "restart_service" => {
                             std::thread::spawn(|| {
                                 let _ = std::process::Command::new("cmd")
@@ -97,6 +118,22 @@ fn main() {
                 })
                 .build(app)?;
 
+            // Share the in-memory cache via managed state.
+            app.manage(cache::global());
+
+            // Push data-file changes to the control panel instead of polling.
+            match watcher::spawn(app.handle()) {
+                Ok(state) => {
+                    app.manage(state);
+                }
+                Err(e) => {
+                    eprintln!("failed to start data watcher: {e}");
+                }
+            }
+
+            // Expose health/stats over HTTP for Prometheus/Grafana.
+            metrics::spawn();
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -108,6 +145,11 @@ fn main() {
             ipc::update_settings,
             ipc::get_settings,
             ipc::get_health_data,
+            ipc::approve_action,
+            ipc::dismiss_action,
+            ipc::get_action_status,
+            backup::export_data,
+            backup::import_data,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
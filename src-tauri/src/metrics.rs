@@ -0,0 +1,120 @@
+//! Embedded HTTP server exposing agent health/stats for external monitoring.
+//!
+//! Bound to a configurable localhost port (`metricsPort` in `agent.config.json`,
+//! default `19851`), it reuses the logic behind `get_stats` and `get_health_data`
+//! to serve:
+//!
+//! * `/healthz`  — `200` if the data dir is readable, `503` otherwise.
+//! * `/stats`    — the [`ipc::Stats`](crate::ipc::Stats) struct as JSON.
+//! * `/metrics`  — Prometheus text exposition format.
+//!
+//! This lets Prometheus/Grafana scrape the agent directly without a separate
+//! exporter.
+
+use std::io::Cursor;
+
+use tiny_http::{Header, Response, Server};
+
+use crate::ipc;
+
+/// Port used when `metricsPort` is absent from the configuration.
+const DEFAULT_PORT: u16 = 19851;
+
+/// Bind the metrics server and serve requests on a background thread.
+pub fn spawn() {
+    let port = ipc::get_settings()
+        .get("metricsPort")
+        .and_then(|v| v.as_u64())
+        .map(|p| p as u16)
+        .unwrap_or(DEFAULT_PORT);
+
+    let addr = format!("127.0.0.1:{port}");
+    let server = match Server::http(&addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("metrics server failed to bind {addr}: {e}");
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let _ = request.respond(route(request.url()));
+        }
+    });
+}
+
+fn route(url: &str) -> Response<Cursor<Vec<u8>>> {
+    // Ignore any query string.
+    let path = url.split('?').next().unwrap_or(url);
+    match path {
+        "/healthz" => healthz(),
+        "/stats" => json_response(&ipc::get_stats()),
+        "/metrics" => text_response(render_metrics()),
+        _ => Response::from_string("not found").with_status_code(404),
+    }
+}
+
+fn healthz() -> Response<Cursor<Vec<u8>>> {
+    if std::fs::read_dir(ipc::get_data_dir()).is_ok() {
+        Response::from_string("ok").with_status_code(200)
+    } else {
+        Response::from_string("data dir unreadable").with_status_code(503)
+    }
+}
+
+fn json_response<T: serde::Serialize>(value: &T) -> Response<Cursor<Vec<u8>>> {
+    let body = serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string());
+    Response::from_string(body).with_header(content_type("application/json"))
+}
+
+fn text_response(body: String) -> Response<Cursor<Vec<u8>>> {
+    Response::from_string(body).with_header(content_type("text/plain; version=0.0.4"))
+}
+
+fn content_type(value: &str) -> Header {
+    Header::from_bytes(&b"Content-Type"[..], value.as_bytes()).unwrap()
+}
+
+/// Render the Prometheus exposition document.
+fn render_metrics() -> String {
+    let stats = serde_json::to_value(ipc::get_stats()).unwrap_or_default();
+    let stat = |key: &str| stats.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+
+    let mut out = String::new();
+    gauge(&mut out, "opsis_issues_detected", "Total issues detected (tickets).", stat("issuesDetected"));
+    gauge(&mut out, "opsis_active_tickets", "Tickets not yet resolved.", stat("activeTickets"));
+    gauge(&mut out, "opsis_issues_escalated", "Issues escalated to a human.", stat("issuesEscalated"));
+    gauge(&mut out, "opsis_success_rate", "Remediation success rate (percent).", stat("successRate"));
+
+    let scores = ipc::build_health_scores();
+    if let Some(resources) = scores.as_object() {
+        out.push_str("# HELP opsis_health_score Per-resource health score (0-100).\n");
+        out.push_str("# TYPE opsis_health_score gauge\n");
+        for (name, value) in resources {
+            let score = value.get("score").and_then(|s| s.as_i64()).unwrap_or(0);
+            out.push_str(&format!(
+                "opsis_health_score{{resource=\"{}\"}} {}\n",
+                escape_label(name),
+                score
+            ));
+        }
+    }
+
+    out
+}
+
+/// Append a single-sample gauge with its `# HELP`/`# TYPE` headers.
+fn gauge(out: &mut String, name: &str, help: &str, value: i64) {
+    out.push_str(&format!("# HELP {name} {help}\n"));
+    out.push_str(&format!("# TYPE {name} gauge\n"));
+    out.push_str(&format!("{name} {value}\n"));
+}
+
+/// Escape a Prometheus label value per the exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,188 @@
+//! Data-directory watcher that pushes changes to the control panel.
+//!
+//! Instead of the frontend polling `get_stats`/`get_tickets`/`get_health_data`,
+//! this subsystem watches the JSON files the service process writes and emits
+//! typed Tauri events (`tickets-changed`, `health-changed`, `proactive-changed`)
+//! to the `main` window carrying the freshly recomputed payload. Rapid writes
+//! are coalesced within a short debounce window, and a fallback poll covers
+//! filesystems where `notify` events are unreliable.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, SystemTime};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter};
+
+use crate::ipc;
+
+/// Data files the watcher tracks, in the order they are scanned.
+const WATCHED_FILES: &[&str] = &[
+    "tickets.json",
+    "state-tracker.json",
+    "pattern-detector.json",
+    "pending-actions.json",
+];
+
+/// Coalesce writes that land within this window into a single emit.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+/// Re-check the watched files' mtimes this often even without a `notify` event
+/// and emit for any that moved, so the panel stays correct on filesystems where
+/// file-change events don't fire.
+const FALLBACK_POLL: Duration = Duration::from_secs(10);
+
+/// Held in Tauri's managed state so the OS watcher (and its background thread)
+/// live for the lifetime of the app rather than being dropped after `setup`.
+pub struct WatcherState {
+    _watcher: RecommendedWatcher,
+}
+
+/// Start watching the data directory and spawn the debounce/emit loop.
+pub fn spawn(app: &AppHandle) -> notify::Result<WatcherState> {
+    let data_dir = ipc::get_data_dir();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })?;
+    watcher.watch(&data_dir, RecursiveMode::NonRecursive)?;
+
+    let app = app.clone();
+    std::thread::spawn(move || debounce_loop(app, rx));
+
+    Ok(WatcherState { _watcher: watcher })
+}
+
+/// Drain change events, coalescing bursts, and emit the affected payloads.
+fn debounce_loop(app: AppHandle, rx: mpsc::Receiver<notify::Event>) {
+    // Last-seen mtime per watched file, so the fallback poll can tell a real
+    // missed write from an idle tick.
+    let mut last_seen: HashMap<&str, Option<SystemTime>> =
+        WATCHED_FILES.iter().map(|f| (*f, file_mtime(f))).collect();
+
+    loop {
+        // Block until something changes or the fallback timer elapses.
+        let first = match rx.recv_timeout(FALLBACK_POLL) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => return,
+        };
+
+        let mut changed = Changed::default();
+
+        let Some(first) = first else {
+            // Fallback poll: only act on files whose mtime actually moved, so a
+            // quiet filesystem doesn't re-parse everything and fire spurious
+            // events every interval.
+            for &file in WATCHED_FILES {
+                let current = file_mtime(file);
+                if last_seen.get(file).copied().flatten() != current {
+                    changed.note_file(file);
+                    last_seen.insert(file, current);
+                }
+            }
+            emit(&app, &changed);
+            continue;
+        };
+
+        changed.note(&first);
+
+        // Coalesce any further writes that arrive within the debounce window.
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(event) => changed.note(&event),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        // Record the post-write mtimes so the fallback poll doesn't re-fire for
+        // writes we just handled.
+        for &file in WATCHED_FILES {
+            last_seen.insert(file, file_mtime(file));
+        }
+
+        emit(&app, &changed);
+    }
+}
+
+fn file_mtime(file: &str) -> Option<SystemTime> {
+    std::fs::metadata(ipc::get_data_dir().join(file))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// Invalidate the cache and emit the payloads for whichever panels changed.
+fn emit(app: &AppHandle, changed: &Changed) {
+    if !changed.any() {
+        return;
+    }
+
+    // Invalidate the cache so the recomputed payloads reflect the writes.
+    crate::cache::global().invalidate_all();
+
+    if changed.tickets {
+        emit_tickets(app);
+    }
+    if changed.health {
+        emit_health(app);
+    }
+    if changed.proactive {
+        emit_proactive(app);
+    }
+}
+
+/// Which panels a batch of filesystem events touched.
+#[derive(Default)]
+struct Changed {
+    tickets: bool,
+    health: bool,
+    proactive: bool,
+}
+
+impl Changed {
+    fn note(&mut self, event: &notify::Event) {
+        for path in &event.paths {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                self.note_file(name);
+            }
+        }
+    }
+
+    fn note_file(&mut self, name: &str) {
+        match name {
+            "tickets.json" => self.tickets = true,
+            "state-tracker.json" | "pattern-detector.json" => self.health = true,
+            "pending-actions.json" => {
+                self.proactive = true;
+                // Pending actions also feed the health payload.
+                self.health = true;
+            }
+            _ => {}
+        }
+    }
+
+    fn any(&self) -> bool {
+        self.tickets || self.health || self.proactive
+    }
+}
+
+fn emit_tickets(app: &AppHandle) {
+    let _ = app.emit(
+        "tickets-changed",
+        serde_json::json!({
+            "stats": ipc::get_stats(),
+            "tickets": ipc::get_tickets(),
+        }),
+    );
+}
+
+fn emit_health(app: &AppHandle) {
+    let _ = app.emit("health-changed", ipc::get_health_data());
+}
+
+fn emit_proactive(app: &AppHandle) {
+    let _ = app.emit("proactive-changed", ipc::build_proactive_actions());
+}